@@ -0,0 +1,341 @@
+use std::fs::read_to_string;
+use std::io::Error;
+use std::io::ErrorKind;
+use std::io::Result;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::inspect::FindAddrOpts;
+use crate::inspect::SymInfo;
+use crate::symbolize::AddrLineInfo;
+use crate::Addr;
+use crate::SymResolver;
+
+
+/// A single line number record, as found trailing a `FUNC` record in a
+/// Breakpad symbol file.
+#[derive(Debug)]
+struct BreakpadLine {
+    addr: Addr,
+    size: Addr,
+    line: usize,
+    file_idx: usize,
+}
+
+/// A `FUNC` record.
+#[derive(Debug)]
+struct BreakpadFunc {
+    addr: Addr,
+    size: Addr,
+    name: String,
+    /// Line number records belonging to this function, sorted by `addr`.
+    lines: Vec<BreakpadLine>,
+}
+
+/// A `PUBLIC` record.
+#[derive(Debug)]
+struct BreakpadPublic {
+    addr: Addr,
+    name: String,
+}
+
+fn parse_hex(s: &str) -> Result<Addr> {
+    u64::from_str_radix(s, 16)
+        .map(|value| value as Addr)
+        .map_err(|_err| Error::new(ErrorKind::InvalidData, format!("invalid hex value: {s}")))
+}
+
+fn parse_dec(s: &str) -> Result<usize> {
+    s.parse::<usize>()
+        .map_err(|_err| Error::new(ErrorKind::InvalidData, format!("invalid decimal value: {s}")))
+}
+
+/// A parsed Breakpad (`.sym`) symbol file.
+///
+/// Breakpad symbol files are a line oriented text format emitted by
+/// `minidump` tooling as a portable, debugger-independent stand-in for
+/// the original ELF/DWARF debug information. We only care about the
+/// subset of records necessary to resolve an address to a symbol name
+/// and source line:
+/// - `FILE <number> <name>`
+/// - `FUNC [m] <address> <size> <param_size> <name>`
+/// - `<address> <size> <line> <filenum>` (a line record, following a `FUNC`)
+/// - `PUBLIC [m] <address> <param_size> <name>`
+#[derive(Debug)]
+struct BreakpadParser {
+    files: Vec<String>,
+    funcs: Vec<BreakpadFunc>,
+    publics: Vec<BreakpadPublic>,
+}
+
+impl BreakpadParser {
+    fn parse(data: &str) -> Result<Self> {
+        let mut files = Vec::new();
+        let mut funcs = Vec::new();
+        let mut publics = Vec::new();
+        let mut current_func: Option<BreakpadFunc> = None;
+
+        for line in data.lines() {
+            let mut tokens = line.split_whitespace();
+            match tokens.next() {
+                Some("MODULE") => {
+                    // `MODULE <os> <arch> <id> <name>`; nothing in here is
+                    // needed for symbolization.
+                }
+                Some("FILE") => {
+                    let number = parse_dec(tokens.next().ok_or_else(|| {
+                        Error::new(ErrorKind::InvalidData, "FILE record without number")
+                    })?)?;
+                    let name = tokens.collect::<Vec<_>>().join(" ");
+                    if files.len() <= number {
+                        files.resize(number + 1, String::new());
+                    }
+                    files[number] = name;
+                }
+                Some("FUNC") => {
+                    if let Some(func) = current_func.take() {
+                        funcs.push(func);
+                    }
+
+                    let mut next = tokens.next().ok_or_else(|| {
+                        Error::new(ErrorKind::InvalidData, "FUNC record is empty")
+                    })?;
+                    // An optional `m` marker designates multiple symbols
+                    // sharing the same address; it does not affect how we
+                    // resolve addresses.
+                    if next == "m" {
+                        next = tokens.next().ok_or_else(|| {
+                            Error::new(ErrorKind::InvalidData, "FUNC record without address")
+                        })?;
+                    }
+
+                    let addr = parse_hex(next)?;
+                    let size = parse_hex(tokens.next().ok_or_else(|| {
+                        Error::new(ErrorKind::InvalidData, "FUNC record without size")
+                    })?)?;
+                    // `param_size` is irrelevant for symbolization.
+                    let _param_size = tokens.next();
+                    let name = tokens.collect::<Vec<_>>().join(" ");
+
+                    current_func = Some(BreakpadFunc {
+                        addr,
+                        size,
+                        name,
+                        lines: Vec::new(),
+                    });
+                }
+                Some("PUBLIC") => {
+                    if let Some(func) = current_func.take() {
+                        funcs.push(func);
+                    }
+
+                    let mut next = tokens.next().ok_or_else(|| {
+                        Error::new(ErrorKind::InvalidData, "PUBLIC record is empty")
+                    })?;
+                    if next == "m" {
+                        next = tokens.next().ok_or_else(|| {
+                            Error::new(ErrorKind::InvalidData, "PUBLIC record without address")
+                        })?;
+                    }
+
+                    let addr = parse_hex(next)?;
+                    let _param_size = tokens.next();
+                    let name = tokens.collect::<Vec<_>>().join(" ");
+                    publics.push(BreakpadPublic { addr, name });
+                }
+                Some("STACK") | Some("INLINE") => {
+                    // Call frame information and inlining records are not
+                    // relevant to straight line/symbol resolution.
+                }
+                Some(first) if first.bytes().all(|b| b.is_ascii_hexdigit()) => {
+                    // A bare line record: `<address> <size> <line> <filenum>`.
+                    let func = current_func.as_mut().ok_or_else(|| {
+                        Error::new(ErrorKind::InvalidData, "line record without preceding FUNC")
+                    })?;
+                    let addr = parse_hex(first)?;
+                    let size = parse_hex(tokens.next().ok_or_else(|| {
+                        Error::new(ErrorKind::InvalidData, "line record without size")
+                    })?)?;
+                    let line = parse_dec(tokens.next().ok_or_else(|| {
+                        Error::new(ErrorKind::InvalidData, "line record without line number")
+                    })?)?;
+                    let file_idx = parse_dec(tokens.next().ok_or_else(|| {
+                        Error::new(ErrorKind::InvalidData, "line record without file number")
+                    })?)?;
+                    func.lines.push(BreakpadLine {
+                        addr,
+                        size,
+                        line,
+                        file_idx,
+                    });
+                }
+                Some(_) | None => {
+                    // Unrecognized or blank lines are ignored; the format is
+                    // meant to be forward compatible with records we don't
+                    // understand yet.
+                }
+            }
+        }
+
+        if let Some(func) = current_func.take() {
+            funcs.push(func);
+        }
+
+        funcs.sort_by_key(|func| func.addr);
+        for func in &mut funcs {
+            func.lines.sort_by_key(|line| line.addr);
+        }
+        publics.sort_by_key(|public| public.addr);
+
+        Ok(Self {
+            files,
+            funcs,
+            publics,
+        })
+    }
+
+    /// Find the `FUNC` record covering `addr`, if any.
+    fn find_func(&self, addr: Addr) -> Option<&BreakpadFunc> {
+        let idx = self
+            .funcs
+            .partition_point(|func| func.addr <= addr)
+            .checked_sub(1)?;
+        let func = &self.funcs[idx];
+        (addr < func.addr + func.size).then_some(func)
+    }
+
+    /// Find the nearest `PUBLIC` record at or preceding `addr`.
+    fn find_public(&self, addr: Addr) -> Option<&BreakpadPublic> {
+        let idx = self
+            .publics
+            .partition_point(|public| public.addr <= addr)
+            .checked_sub(1)?;
+        Some(&self.publics[idx])
+    }
+
+    /// Find the line record covering `addr` within `func`.
+    fn find_line<'slf>(&'slf self, func: &'slf BreakpadFunc, addr: Addr) -> Option<AddrLineInfo> {
+        let idx = func
+            .lines
+            .partition_point(|line| line.addr <= addr)
+            .checked_sub(1)?;
+        let line = &func.lines[idx];
+        if addr >= line.addr + line.size {
+            return None
+        }
+
+        let path = self
+            .files
+            .get(line.file_idx)
+            .map(PathBuf::from)
+            .unwrap_or_default();
+        Some(AddrLineInfo {
+            path,
+            line: line.line,
+            column: 0,
+        })
+    }
+}
+
+
+/// A symbol resolver backed by a Breakpad (`.sym`) symbol file.
+pub(crate) struct BreakpadResolver {
+    parser: BreakpadParser,
+    path: Option<PathBuf>,
+}
+
+impl BreakpadResolver {
+    /// Create a new [`BreakpadResolver`] from the symbol file at `path`.
+    pub(crate) fn new(path: &Path) -> Result<Self> {
+        let data = read_to_string(path)?;
+        let parser = BreakpadParser::parse(&data)?;
+        Ok(Self {
+            parser,
+            path: Some(path.to_path_buf()),
+        })
+    }
+
+    /// Create a new [`BreakpadResolver`] from "raw" Breakpad symbol data.
+    pub(crate) fn from_data(data: &[u8]) -> Result<Self> {
+        let data = std::str::from_utf8(data)
+            .map_err(|_err| Error::new(ErrorKind::InvalidData, "Breakpad data is not valid UTF-8"))?;
+        let parser = BreakpadParser::parse(data)?;
+        Ok(Self { parser, path: None })
+    }
+}
+
+impl SymResolver for BreakpadResolver {
+    fn find_syms(&self, addr: Addr) -> Result<Vec<(&str, Addr)>> {
+        if let Some(func) = self.parser.find_func(addr) {
+            return Ok(vec![(func.name.as_str(), func.addr)])
+        }
+
+        if let Some(public) = self.parser.find_public(addr) {
+            return Ok(vec![(public.name.as_str(), public.addr)])
+        }
+
+        Ok(Vec::new())
+    }
+
+    fn find_addr(&self, _name: &str, _opts: &FindAddrOpts) -> Result<Vec<SymInfo>> {
+        // Breakpad symbol files are consulted address-to-name only; we have
+        // no use case for the reverse direction yet.
+        Ok(Vec::new())
+    }
+
+    fn find_line_info(&self, addr: Addr) -> Result<Option<AddrLineInfo>> {
+        let func = match self.parser.find_func(addr) {
+            Some(func) => func,
+            None => return Ok(None),
+        };
+        Ok(self.parser.find_line(func, addr))
+    }
+
+    fn addr_file_off(&self, _addr: Addr) -> Option<u64> {
+        None
+    }
+}
+
+impl std::fmt::Debug for BreakpadResolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.path {
+            Some(path) => write!(f, "BreakpadResolver {}", path.display()),
+            None => write!(f, "BreakpadResolver <data>"),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small synthetic Breakpad symbol file exercising `FUNC`, its line
+    /// records, and a trailing `PUBLIC`-only symbol.
+    const SYM_DATA: &str = "\
+MODULE Linux x86_64 000000000000000000000000000000000 a.out
+FILE 0 src/main.rs
+FUNC 1000 20 0 main
+1000 10 10 0
+1010 10 11 0
+PUBLIC 2000 0 _start
+";
+
+    #[test]
+    fn find_syms_and_line_info() {
+        let resolver = BreakpadResolver::from_data(SYM_DATA.as_bytes()).unwrap();
+
+        let syms = resolver.find_syms(0x1005).unwrap();
+        assert_eq!(syms, vec![("main", 0x1000)]);
+
+        let line_info = resolver.find_line_info(0x1015).unwrap().unwrap();
+        assert_eq!(line_info.path, PathBuf::from("src/main.rs"));
+        assert_eq!(line_info.line, 11);
+
+        let syms = resolver.find_syms(0x2000).unwrap();
+        assert_eq!(syms, vec![("_start", 0x2000)]);
+
+        // Below the lowest known `FUNC`/`PUBLIC` address, nothing matches.
+        assert!(resolver.find_syms(0x500).unwrap().is_empty());
+    }
+}
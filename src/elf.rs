@@ -0,0 +1,405 @@
+use std::fs::read;
+use std::io::Error;
+use std::io::ErrorKind;
+use std::io::Result;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::inspect::FindAddrOpts;
+use crate::inspect::SymInfo;
+use crate::symbolize::AddrLineInfo;
+use crate::Addr;
+use crate::SymResolver;
+
+const ELFMAG: &[u8; 4] = b"\x7fELF";
+const ELFCLASS64: u8 = 2;
+const SHT_SYMTAB: u32 = 2;
+const SHT_STRTAB: u32 = 3;
+const SHT_NOTE: u32 = 7;
+const NT_GNU_BUILD_ID: u32 = 3;
+
+/// A single entry of an ELF symbol table that we care about.
+struct ElfSym {
+    name: String,
+    addr: Addr,
+    size: Addr,
+}
+
+/// A minimal, read-only view of the pieces of an ELF file blazesym needs in
+/// order to resolve addresses to symbol names: the section header table, the
+/// symbol table (`.symtab`, falling back to `.dynsym`), and the presence of
+/// `.debug_*` sections.
+pub(crate) struct ElfParser {
+    path: PathBuf,
+    syms: Vec<ElfSym>,
+    has_debug_info: bool,
+    build_id: Option<Vec<u8>>,
+    debug_link: Option<(String, u32)>,
+}
+
+fn u16_at(data: &[u8], off: usize) -> Result<u16> {
+    let bytes = data
+        .get(off..off + 2)
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "ELF file truncated"))?;
+    Ok(u16::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn u32_at(data: &[u8], off: usize) -> Result<u32> {
+    let bytes = data
+        .get(off..off + 4)
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "ELF file truncated"))?;
+    Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn u64_at(data: &[u8], off: usize) -> Result<u64> {
+    let bytes = data
+        .get(off..off + 8)
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "ELF file truncated"))?;
+    Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+/// Parse a `NT_GNU_BUILD_ID` note out of the contents of a
+/// `.note.gnu.build-id` section.
+fn parse_build_id_note(data: &[u8], off: usize, size: usize) -> Option<Vec<u8>> {
+    let note = data.get(off..off + size)?;
+    let namesz = u32::from_le_bytes(note.get(0..4)?.try_into().ok()?) as usize;
+    let descsz = u32::from_le_bytes(note.get(4..8)?.try_into().ok()?) as usize;
+    let kind = u32::from_le_bytes(note.get(8..12)?.try_into().ok()?);
+    if kind != NT_GNU_BUILD_ID {
+        return None
+    }
+
+    // Both `name` and `desc` are padded up to 4-byte alignment.
+    let name_off = 12;
+    let desc_off = name_off + (namesz + 3) / 4 * 4;
+    note.get(desc_off..desc_off + descsz).map(<[u8]>::to_vec)
+}
+
+fn str_at(data: &[u8], off: usize) -> Result<String> {
+    let tail = data
+        .get(off..)
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "ELF file truncated"))?;
+    let end = tail.iter().position(|&b| b == 0).unwrap_or(tail.len());
+    Ok(String::from_utf8_lossy(&tail[..end]).into_owned())
+}
+
+/// Parse a `.gnu_debuglink` section: a NUL-terminated file name, padded to
+/// 4-byte alignment, followed by a little-endian CRC32 of the referenced
+/// file's contents.
+fn parse_debug_link(data: &[u8], off: usize, size: usize) -> Option<(String, u32)> {
+    let section = data.get(off..off + size)?;
+    let name_end = section.iter().position(|&b| b == 0)?;
+    let name = String::from_utf8_lossy(&section[..name_end]).into_owned();
+    let crc_off = (name_end + 1 + 3) / 4 * 4;
+    let crc = u32::from_le_bytes(section.get(crc_off..crc_off + 4)?.try_into().ok()?);
+    Some((name, crc))
+}
+
+/// Compute the CRC32 (zlib/IEEE 802.3 polynomial) of `data`, as used to
+/// validate `.gnu_debuglink` targets.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// The canonical locations a split debug file may live in, relative to the
+/// directory containing the main ELF file, per the `gdb`/`gnu_debuglink`
+/// convention.
+fn debug_link_candidates(elf_path: &Path, debug_name: &str, extra_dirs: &[PathBuf]) -> Vec<PathBuf> {
+    let dir = elf_path.parent().unwrap_or_else(|| Path::new(""));
+    let mut candidates = vec![dir.join(debug_name), dir.join(".debug").join(debug_name)];
+
+    // `/usr/lib/debug/<dir>/` mirrors the *absolute* directory of the main
+    // ELF file; resolve a relative `elf_path` (e.g. from `Elf::new("./a.out")`)
+    // against the current directory first, or the `/usr/lib/debug` candidate
+    // would silently be skipped instead of considered.
+    let absolute_dir = std::path::absolute(dir).ok();
+    if let Some(dir_in_debug_root) = absolute_dir.as_deref().and_then(|dir| dir.strip_prefix("/").ok()) {
+        candidates.push(Path::new("/usr/lib/debug").join(dir_in_debug_root).join(debug_name));
+    }
+
+    candidates.extend(extra_dirs.iter().map(|extra| extra.join(debug_name)));
+    candidates
+}
+
+/// The `/usr/lib/debug/.build-id/<xx>/<rest>.debug` location for a build-id.
+fn build_id_debug_path(build_id: &[u8]) -> Option<PathBuf> {
+    if build_id.is_empty() {
+        return None
+    }
+    let hex: String = build_id.iter().map(|byte| format!("{byte:02x}")).collect();
+    let (prefix, rest) = hex.split_at(2);
+    Some(
+        Path::new("/usr/lib/debug/.build-id")
+            .join(prefix)
+            .join(format!("{rest}.debug")),
+    )
+}
+
+/// Locate a companion debug file for `elf_path`, following `.gnu_debuglink`
+/// and `.build-id` conventions, validating the CRC32 of any debug-link
+/// candidate before accepting it.
+fn find_debug_file(
+    elf_path: &Path,
+    build_id: Option<&[u8]>,
+    debug_link: Option<&(String, u32)>,
+    extra_dirs: &[PathBuf],
+) -> Option<PathBuf> {
+    if let Some(build_id) = build_id {
+        if let Some(path) = build_id_debug_path(build_id) {
+            if path.is_file() {
+                return Some(path)
+            }
+        }
+    }
+
+    let (name, crc) = debug_link?;
+    debug_link_candidates(elf_path, name, extra_dirs)
+        .into_iter()
+        .find(|candidate| read(candidate).map(|data| crc32(&data) == *crc).unwrap_or(false))
+}
+
+impl ElfParser {
+    pub(crate) fn open(path: &Path) -> Result<Self> {
+        let data = read(path)?;
+        Self::parse(path, &data)
+    }
+
+    fn parse(path: &Path, data: &[u8]) -> Result<Self> {
+        if data.len() < 64 || &data[0..4] != ELFMAG {
+            return Err(Error::new(ErrorKind::InvalidData, "not an ELF file"))
+        }
+        if data[4] != ELFCLASS64 {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "only 64-bit ELF files are supported",
+            ))
+        }
+
+        let e_shoff = u64_at(data, 0x28)? as usize;
+        let e_shentsize = u16_at(data, 0x3a)? as usize;
+        let e_shnum = u16_at(data, 0x3c)? as usize;
+        let e_shstrndx = u16_at(data, 0x3e)? as usize;
+
+        let shdr_at = |idx: usize| e_shoff + idx * e_shentsize;
+        let shstrtab_off = u64_at(data, shdr_at(e_shstrndx) + 0x18)? as usize;
+
+        let mut syms = Vec::new();
+        let mut has_debug_info = false;
+        let mut build_id = None;
+        let mut debug_link = None;
+        let mut symtab: Option<(usize, usize, usize)> = None;
+        let mut dynsym: Option<(usize, usize, usize)> = None;
+
+        for idx in 0..e_shnum {
+            let base = shdr_at(idx);
+            let sh_name = u32_at(data, base)? as usize;
+            let sh_type = u32_at(data, base + 0x04)?;
+            let sh_offset = u64_at(data, base + 0x18)? as usize;
+            let sh_size = u64_at(data, base + 0x20)? as usize;
+            let sh_link = u32_at(data, base + 0x28)? as usize;
+            let name = str_at(data, shstrtab_off + sh_name)?;
+
+            if name.starts_with(".debug_") {
+                has_debug_info = true;
+            }
+
+            if sh_type == SHT_SYMTAB {
+                symtab = Some((sh_offset, sh_size, sh_link));
+            } else if sh_type == SHT_STRTAB && name == ".dynstr" {
+                // Recorded via `sh_link` of `.dynsym` below; nothing to do
+                // here directly.
+            } else if name == ".dynsym" {
+                dynsym = Some((sh_offset, sh_size, sh_link));
+            } else if sh_type == SHT_NOTE && name == ".note.gnu.build-id" {
+                build_id = parse_build_id_note(data, sh_offset, sh_size);
+            } else if name == ".gnu_debuglink" {
+                debug_link = parse_debug_link(data, sh_offset, sh_size);
+            }
+        }
+
+        let (sym_off, sym_size, link) = symtab.or(dynsym).unwrap_or((0, 0, 0));
+        if sym_size > 0 {
+            let strtab_base = shdr_at(link);
+            let strtab_off = u64_at(data, strtab_base + 0x18)? as usize;
+
+            const SYM_ENTSIZE: usize = 24;
+            let count = sym_size / SYM_ENTSIZE;
+            for i in 0..count {
+                let base = sym_off + i * SYM_ENTSIZE;
+                let st_name = u32_at(data, base)? as usize;
+                let st_value = u64_at(data, base + 0x08)? as Addr;
+                let st_size = u64_at(data, base + 0x10)? as Addr;
+                if st_name == 0 || st_value == 0 {
+                    continue
+                }
+                syms.push(ElfSym {
+                    name: str_at(data, strtab_off + st_name)?,
+                    addr: st_value,
+                    size: st_size,
+                });
+            }
+        }
+
+        syms.sort_by_key(|sym| sym.addr);
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            syms,
+            has_debug_info,
+            build_id,
+            debug_link,
+        })
+    }
+
+    fn find_sym(&self, addr: Addr) -> Option<&ElfSym> {
+        let idx = self.syms.partition_point(|sym| sym.addr <= addr).checked_sub(1)?;
+        let sym = &self.syms[idx];
+        (sym.size == 0 || addr < sym.addr + sym.size).then_some(sym)
+    }
+}
+
+
+/// A symbol resolver backed by an ELF file's symbol table.
+///
+/// DWARF debug information is not read: no line information is reported
+/// for any address.
+///
+/// When the main ELF file is stripped, [`ElfResolver::open`] transparently
+/// follows `.gnu_debuglink`/`.build-id` to a separate debug file and
+/// resolves symbols from there instead.
+pub(crate) struct ElfResolver {
+    parser: ElfParser,
+    debug_parser: Option<ElfParser>,
+}
+
+impl ElfResolver {
+    /// Open the ELF file at `path` for symbol resolution, searching only the
+    /// standard debug file locations.
+    pub(crate) fn open(path: &Path) -> Result<Self> {
+        Self::open_with_debug_dirs(path, &[])
+    }
+
+    /// Open the ELF file at `path`, additionally searching `extra_debug_dirs`
+    /// for a split debug file if the main file lacks debug information.
+    pub(crate) fn open_with_debug_dirs(path: &Path, extra_debug_dirs: &[PathBuf]) -> Result<Self> {
+        let parser = ElfParser::open(path)?;
+        let debug_parser = if parser.has_debug_info {
+            None
+        } else {
+            find_debug_file(
+                path,
+                parser.build_id.as_deref(),
+                parser.debug_link.as_ref(),
+                extra_debug_dirs,
+            )
+            .and_then(|debug_path| ElfParser::open(&debug_path).ok())
+        };
+
+        Ok(Self {
+            parser,
+            debug_parser,
+        })
+    }
+
+    /// Check whether the underlying ELF file carries `.debug_*` sections,
+    /// whether in the main file or a resolved companion debug file.
+    pub(crate) fn has_debug_info(&self) -> bool {
+        self.parser.has_debug_info
+            || self
+                .debug_parser
+                .as_ref()
+                .map_or(false, |parser| parser.has_debug_info)
+    }
+
+    /// The path of the underlying ELF file.
+    pub(crate) fn file_name(&self) -> &Path {
+        &self.parser.path
+    }
+
+    /// The file's `NT_GNU_BUILD_ID` note contents, if present.
+    pub(crate) fn build_id(&self) -> Option<&[u8]> {
+        self.parser.build_id.as_deref()
+    }
+}
+
+impl SymResolver for ElfResolver {
+    fn find_syms(&self, addr: Addr) -> Result<Vec<(&str, Addr)>> {
+        let parser = self.debug_parser.as_ref().unwrap_or(&self.parser);
+        Ok(parser
+            .find_sym(addr)
+            .map(|sym| vec![(sym.name.as_str(), sym.addr)])
+            .unwrap_or_default())
+    }
+
+    fn find_addr(&self, _name: &str, _opts: &FindAddrOpts) -> Result<Vec<SymInfo>> {
+        Ok(Vec::new())
+    }
+
+    fn find_line_info(&self, _addr: Addr) -> Result<Option<AddrLineInfo>> {
+        // The `.debug_line` section is not parsed, so there is no line
+        // information to report.
+        Ok(None)
+    }
+
+    fn addr_file_off(&self, _addr: Addr) -> Option<u64> {
+        None
+    }
+}
+
+impl std::fmt::Debug for ElfResolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ElfResolver {}", self.parser.path.display())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs::create_dir_all;
+    use std::fs::remove_dir_all;
+    use std::fs::write;
+
+    /// Check our CRC32 implementation against the well-known check value for
+    /// the ASCII string `"123456789"`.
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+
+    /// Check that `find_debug_file` accepts a `.gnu_debuglink` candidate only
+    /// when its CRC32 matches, and otherwise rejects it.
+    #[test]
+    fn find_debug_file_via_gnu_debuglink() {
+        let dir = std::env::temp_dir().join("blazesym-test-find-debug-file-via-gnu-debuglink");
+        let _ = remove_dir_all(&dir);
+        create_dir_all(&dir).unwrap();
+
+        let debug_name = "a.out.debug";
+        let debug_contents = b"fake split debug file contents";
+        let debug_path = dir.join(debug_name);
+        write(&debug_path, debug_contents).unwrap();
+
+        let elf_path = dir.join("a.out");
+        let crc = crc32(debug_contents);
+        let debug_link = (debug_name.to_string(), crc);
+
+        let found = find_debug_file(&elf_path, None, Some(&debug_link), &[]);
+        assert_eq!(found, Some(debug_path));
+
+        let mismatched_link = (debug_name.to_string(), crc.wrapping_add(1));
+        let not_found = find_debug_file(&elf_path, None, Some(&mismatched_link), &[]);
+        assert_eq!(not_found, None);
+
+        let _ = remove_dir_all(&dir);
+    }
+}
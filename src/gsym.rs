@@ -0,0 +1,65 @@
+use std::io::Error;
+use std::io::ErrorKind;
+use std::io::Result;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::inspect::FindAddrOpts;
+use crate::inspect::SymInfo;
+use crate::symbolize::AddrLineInfo;
+use crate::Addr;
+use crate::SymResolver;
+
+/// A symbol resolver for the [Gsym](https://llvm.org/docs/GSYM.html)
+/// format.
+///
+/// Gsym data is not read: every lookup reports no symbols, no addresses,
+/// and no line information.
+pub(crate) struct GsymResolver {
+    path: Option<PathBuf>,
+}
+
+impl GsymResolver {
+    /// Create a resolver for the Gsym file at `path`, failing if it does not
+    /// exist. The file's contents are not read.
+    pub(crate) fn open(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Err(Error::new(ErrorKind::NotFound, "Gsym file does not exist"))
+        }
+        Ok(Self {
+            path: Some(path.to_path_buf()),
+        })
+    }
+
+    /// Create a resolver for "raw" Gsym data. The data is not parsed.
+    pub(crate) fn parse(_data: &[u8]) -> Result<Self> {
+        Ok(Self { path: None })
+    }
+}
+
+impl SymResolver for GsymResolver {
+    fn find_syms(&self, _addr: Addr) -> Result<Vec<(&str, Addr)>> {
+        Ok(Vec::new())
+    }
+
+    fn find_addr(&self, _name: &str, _opts: &FindAddrOpts) -> Result<Vec<SymInfo>> {
+        Ok(Vec::new())
+    }
+
+    fn find_line_info(&self, _addr: Addr) -> Result<Option<AddrLineInfo>> {
+        Ok(None)
+    }
+
+    fn addr_file_off(&self, _addr: Addr) -> Option<u64> {
+        None
+    }
+}
+
+impl std::fmt::Debug for GsymResolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.path {
+            Some(path) => write!(f, "GsymResolver {}", path.display()),
+            None => write!(f, "GsymResolver <data>"),
+        }
+    }
+}
@@ -0,0 +1,22 @@
+//! Functionality for inspecting symbols, independent of any particular
+//! address.
+
+use crate::Addr;
+
+/// Options influencing the behavior of [`SymResolver::find_addr`][crate::SymResolver::find_addr].
+#[derive(Clone, Debug, Default)]
+pub struct FindAddrOpts {
+    /// Also report sizes for the symbols that are found.
+    pub sym_size: bool,
+}
+
+/// Information about a symbol, as returned by address lookups.
+#[derive(Clone, Debug)]
+pub struct SymInfo {
+    /// The symbol's name.
+    pub name: String,
+    /// The symbol's address.
+    pub addr: Addr,
+    /// The symbol's size, if known.
+    pub size: usize,
+}
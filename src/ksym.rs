@@ -0,0 +1,65 @@
+use std::fs::read_to_string;
+use std::io::Error;
+use std::io::ErrorKind;
+use std::io::Result;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::Addr;
+
+/// A single `/proc/kallsyms` entry.
+struct KSym {
+    addr: Addr,
+    name: String,
+}
+
+/// A symbol resolver backed by a copy of `/proc/kallsyms`.
+pub(crate) struct KSymResolver {
+    path: PathBuf,
+    syms: Vec<KSym>,
+}
+
+impl KSymResolver {
+    /// Load kernel symbols from the kallsyms-formatted file at `path`.
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        let content = read_to_string(path)?;
+        let mut syms = content
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                let addr = u64::from_str_radix(fields.next()?, 16).ok()? as Addr;
+                // The symbol type field is irrelevant for lookups.
+                let _kind = fields.next()?;
+                let name = fields.next()?.to_string();
+                (addr != 0).then_some(KSym { addr, name })
+            })
+            .collect::<Vec<_>>();
+        syms.sort_by_key(|sym| sym.addr);
+
+        if syms.is_empty() {
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                "kallsyms file contained no usable symbols",
+            ))
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            syms,
+        })
+    }
+
+    /// The path of the kallsyms file backing this resolver.
+    pub(crate) fn file_name(&self) -> &Path {
+        &self.path
+    }
+
+    pub(crate) fn find_syms(&self, addr: Addr) -> Result<Vec<(&str, Addr)>> {
+        let idx = match self.syms.partition_point(|sym| sym.addr <= addr).checked_sub(1) {
+            Some(idx) => idx,
+            None => return Ok(Vec::new()),
+        };
+        let sym = &self.syms[idx];
+        Ok(vec![(sym.name.as_str(), sym.addr)])
+    }
+}
@@ -0,0 +1,73 @@
+//! `blazesym` is a library that can be used to symbolize addresses.
+//!
+//! Symbolization is the process of mapping an address to the
+//! symbol (function or variable name) and potentially source
+//! location (file & line number) that it belongs to.
+
+mod breakpad;
+mod debuginfod;
+mod elf;
+mod gsym;
+mod kernel;
+mod ksym;
+mod minidump;
+
+pub mod inspect;
+pub mod symbolize;
+
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::io::Result;
+
+use inspect::FindAddrOpts;
+use inspect::SymInfo;
+use symbolize::AddrLineInfo;
+
+/// An address, as used by this crate.
+pub type Addr = usize;
+
+/// A process identifier, as used for symbolizing addresses of a live
+/// process.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Pid {
+    /// The current process.
+    Slf,
+    /// A process identified by its numeric ID.
+    Pid(u32),
+}
+
+impl From<u32> for Pid {
+    fn from(pid: u32) -> Self {
+        Pid::Pid(pid)
+    }
+}
+
+impl Display for Pid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Pid::Slf => write!(f, "self"),
+            Pid::Pid(pid) => write!(f, "{pid}"),
+        }
+    }
+}
+
+
+/// A trait providing the ability to resolve addresses to symbol information.
+///
+/// Implementations back the various [`symbolize::Source`] variants. Users
+/// can implement this trait for their own symbol sources -- for example, a
+/// JIT code map or a symbol table shipped by a BPF program -- and plug them
+/// in via [`symbolize::Source::Custom`][symbolize::Source].
+pub trait SymResolver: Debug {
+    /// Find the symbol(s) overlapping with `addr`, along with the address at
+    /// which each symbol starts.
+    fn find_syms(&self, addr: Addr) -> Result<Vec<(&str, Addr)>>;
+    /// Find address information for a symbol by name.
+    fn find_addr(&self, name: &str, opts: &FindAddrOpts) -> Result<Vec<SymInfo>>;
+    /// Find line number information for `addr`, if available.
+    fn find_line_info(&self, addr: Addr) -> Result<Option<AddrLineInfo>>;
+    /// Translate a virtual address into a file offset, if possible.
+    fn addr_file_off(&self, addr: Addr) -> Option<u64>;
+}
@@ -0,0 +1,162 @@
+//! Parsing of the portions of the minidump format needed to recover a
+//! process' module list, so that addresses captured in the dump can be
+//! mapped back to the module -- and offset within it -- they belong to.
+
+use std::io::Error;
+use std::io::ErrorKind;
+use std::io::Result;
+use std::path::PathBuf;
+
+use crate::Addr;
+
+/// The `MDMP` magic number found at the start of every minidump file.
+const MINIDUMP_SIGNATURE: u32 = 0x504d444d;
+/// The stream type identifying a `MINIDUMP_MODULE_LIST` stream.
+const MODULE_LIST_STREAM: u32 = 4;
+/// `sizeof(MINIDUMP_MODULE)`.
+const MODULE_ENTRY_SIZE: usize = 108;
+
+fn u32_at(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4)
+        .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn u64_at(data: &[u8], offset: usize) -> Option<u64> {
+    data.get(offset..offset + 8)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+/// Read a `MINIDUMP_STRING` (a length-prefixed, NUL-terminated UTF-16LE
+/// string) located at `rva`.
+fn read_minidump_string(data: &[u8], rva: u32) -> Option<PathBuf> {
+    let offset = rva as usize;
+    let len = u32_at(data, offset)? as usize;
+    let bytes = data.get(offset + 4..offset + 4 + len)?;
+    let units = bytes
+        .chunks_exact(2)
+        .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]));
+    let name = char::decode_utf16(units)
+        .map(|result| result.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect::<String>();
+    Some(PathBuf::from(name))
+}
+
+/// A single module (executable or shared object) recorded in a minidump's
+/// module list.
+#[derive(Clone, Debug)]
+pub(crate) struct MinidumpModule {
+    /// The address at which the module was loaded in the dumped process.
+    pub base: Addr,
+    /// The size, in bytes, of the module's mapped image.
+    pub size: u64,
+    /// The on-disk path of the module, as recorded in the dump.
+    pub path: PathBuf,
+}
+
+/// Parse the module list out of a minidump file's raw bytes.
+pub(crate) fn parse_modules(data: &[u8]) -> Result<Vec<MinidumpModule>> {
+    let invalid = |msg: &'static str| Error::new(ErrorKind::InvalidData, msg);
+
+    if u32_at(data, 0) != Some(MINIDUMP_SIGNATURE) {
+        return Err(invalid("not a minidump file"))
+    }
+    let stream_count = u32_at(data, 8).ok_or_else(|| invalid("truncated minidump header"))?;
+    let stream_dir_rva =
+        u32_at(data, 12).ok_or_else(|| invalid("truncated minidump header"))?;
+
+    let mut modules = Vec::new();
+    for idx in 0..stream_count {
+        let entry_off = stream_dir_rva as usize + idx as usize * 12;
+        let stream_type =
+            u32_at(data, entry_off).ok_or_else(|| invalid("truncated stream directory"))?;
+        if stream_type != MODULE_LIST_STREAM {
+            continue
+        }
+        let stream_rva =
+            u32_at(data, entry_off + 8).ok_or_else(|| invalid("truncated stream directory"))?;
+
+        let module_count =
+            u32_at(data, stream_rva as usize).ok_or_else(|| invalid("truncated module list"))?;
+        for module_idx in 0..module_count {
+            let module_off = stream_rva as usize + 4 + module_idx as usize * MODULE_ENTRY_SIZE;
+            let base =
+                u64_at(data, module_off).ok_or_else(|| invalid("truncated module entry"))?;
+            let size =
+                u32_at(data, module_off + 8).ok_or_else(|| invalid("truncated module entry"))?;
+            let name_rva = u32_at(data, module_off + 20)
+                .ok_or_else(|| invalid("truncated module entry"))?;
+            let path = read_minidump_string(data, name_rva)
+                .ok_or_else(|| invalid("truncated module name"))?;
+
+            modules.push(MinidumpModule {
+                base: base as Addr,
+                size: size as u64,
+                path,
+            });
+        }
+    }
+
+    Ok(modules)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a minimal minidump buffer containing a single `ModuleListStream`
+    /// with a single module entry.
+    fn synthetic_minidump(base: u64, size: u32, name: &str) -> Vec<u8> {
+        let mut data = vec![0u8; 32];
+        data[0..4].copy_from_slice(&MINIDUMP_SIGNATURE.to_le_bytes());
+        // `NumberOfStreams` (1) at offset 8, `StreamDirectoryRva` (32) at
+        // offset 12; the rest of the 32-byte header is unused by our parser.
+        data[8..12].copy_from_slice(&1u32.to_le_bytes());
+        data[12..16].copy_from_slice(&32u32.to_le_bytes());
+
+        // Stream directory: one `MINIDUMP_DIRECTORY` entry (12 bytes),
+        // followed immediately by the `ModuleListStream` payload.
+        let module_list_rva = 32 + 12;
+        data.extend_from_slice(&MODULE_LIST_STREAM.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes()); // DataSize; unused.
+        data.extend_from_slice(&(module_list_rva as u32).to_le_bytes());
+
+        // `ModuleListStream`: `NumberOfModules` followed by `MINIDUMP_MODULE`
+        // entries.
+        data.extend_from_slice(&1u32.to_le_bytes());
+        let module_off = data.len();
+        data.resize(module_off + MODULE_ENTRY_SIZE, 0);
+        data[module_off..module_off + 8].copy_from_slice(&base.to_le_bytes());
+        data[module_off + 8..module_off + 12].copy_from_slice(&size.to_le_bytes());
+        let name_rva = data.len() as u32;
+        data[module_off + 20..module_off + 24].copy_from_slice(&name_rva.to_le_bytes());
+
+        // `MINIDUMP_STRING`: a byte length prefix followed by UTF-16LE units
+        // and a NUL terminator.
+        let units: Vec<u16> = name.encode_utf16().collect();
+        data.extend_from_slice(&((units.len() * 2) as u32).to_le_bytes());
+        for unit in units {
+            data.extend_from_slice(&unit.to_le_bytes());
+        }
+        data.extend_from_slice(&0u16.to_le_bytes());
+
+        data
+    }
+
+    #[test]
+    fn parse_single_module() {
+        let data = synthetic_minidump(0x0040_0000, 0x1000, "/usr/bin/forge");
+        let modules = parse_modules(&data).unwrap();
+
+        assert_eq!(modules.len(), 1);
+        assert_eq!(modules[0].base, 0x0040_0000);
+        assert_eq!(modules[0].size, 0x1000);
+        assert_eq!(modules[0].path, PathBuf::from("/usr/bin/forge"));
+    }
+
+    #[test]
+    fn rejects_non_minidump_data() {
+        let err = parse_modules(b"not a minidump").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+}
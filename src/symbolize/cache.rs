@@ -0,0 +1,195 @@
+use std::cell::RefCell;
+use std::io::Result;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use crate::Addr;
+use crate::Pid;
+use crate::SymResolver;
+
+/// A key identifying a cached resolver.
+///
+/// Besides a plain file path, addresses belonging to a live process are
+/// keyed by the combination of PID and the identity of the
+/// `/proc/<pid>/maps` entry (the mapped file's path and the mapping's start
+/// address) they fall into, so that re-exec'd or otherwise changed mappings
+/// do not get served a stale resolver.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub(crate) enum CacheKey {
+    Path(PathBuf),
+    ProcessModule {
+        pid: Pid,
+        path: PathBuf,
+        start: Addr,
+    },
+    Kernel {
+        kallsyms: Option<PathBuf>,
+        kernel_image: Option<PathBuf>,
+    },
+}
+
+/// A bounded, least-recently-used cache of [`SymResolver`] instances.
+///
+/// Building a resolver (reading and parsing ELF files) is expensive; this
+/// cache lets a [`Symbolizer`][super::Symbolizer] reuse resolvers across
+/// `symbolize` calls, which matters for a long-running tracer resolving
+/// many stack traces from the same set of processes and shared objects.
+#[derive(Debug)]
+pub(crate) struct ResolverCache {
+    capacity: usize,
+    /// Entries ordered from least- to most-recently used.
+    entries: RefCell<Vec<(CacheKey, Rc<dyn SymResolver>)>>,
+}
+
+impl ResolverCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Retrieve the resolver cached under `key`, or create and cache one
+    /// using `create` on a miss.
+    pub(crate) fn get_or_insert_with(
+        &self,
+        key: CacheKey,
+        create: impl FnOnce() -> Result<Rc<dyn SymResolver>>,
+    ) -> Result<Rc<dyn SymResolver>> {
+        if self.capacity == 0 {
+            return create()
+        }
+
+        let mut entries = self.entries.borrow_mut();
+        if let Some(idx) = entries.iter().position(|(k, _)| *k == key) {
+            let (key, resolver) = entries.remove(idx);
+            entries.push((key, resolver.clone()));
+            return Ok(resolver)
+        }
+        drop(entries);
+
+        let resolver = create()?;
+
+        let mut entries = self.entries.borrow_mut();
+        if entries.len() >= self.capacity {
+            // The least-recently used entry lives at the front.
+            let _evicted = entries.remove(0);
+        }
+        entries.push((key, Rc::clone(&resolver)));
+        Ok(resolver)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::io::Result as IoResult;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::inspect::FindAddrOpts;
+    use crate::inspect::SymInfo;
+    use crate::symbolize::AddrLineInfo;
+
+    /// A resolver stub that resolves nothing.
+    #[derive(Debug)]
+    struct DummyResolver;
+
+    impl SymResolver for DummyResolver {
+        fn find_syms(&self, _addr: Addr) -> IoResult<Vec<(&str, Addr)>> {
+            Ok(Vec::new())
+        }
+
+        fn find_addr(&self, _name: &str, _opts: &FindAddrOpts) -> IoResult<Vec<SymInfo>> {
+            Ok(Vec::new())
+        }
+
+        fn find_line_info(&self, _addr: Addr) -> IoResult<Option<AddrLineInfo>> {
+            Ok(None)
+        }
+
+        fn addr_file_off(&self, _addr: Addr) -> Option<u64> {
+            None
+        }
+    }
+
+    /// Create a [`DummyResolver`], counting the builds in `builds` so tests
+    /// can tell a cache hit from a fresh build.
+    fn counting_resolver(builds: &Cell<usize>) -> IoResult<Rc<dyn SymResolver>> {
+        builds.set(builds.get() + 1);
+        Ok(Rc::new(DummyResolver) as Rc<dyn SymResolver>)
+    }
+
+    /// A cache hit on the same key must not invoke `create` again.
+    #[test]
+    fn hit_reuses_cached_resolver() {
+        let cache = ResolverCache::new(2);
+        let builds = Cell::new(0);
+        let key = CacheKey::Path(PathBuf::from("/bin/a.out"));
+
+        let first = cache.get_or_insert_with(key.clone(), || counting_resolver(&builds)).unwrap();
+        let second = cache.get_or_insert_with(key, || counting_resolver(&builds)).unwrap();
+
+        assert_eq!(builds.get(), 1);
+        assert!(Rc::ptr_eq(&first, &second));
+    }
+
+    /// Distinct keys must never alias to the same resolver, even when they
+    /// share some fields (e.g. two `Kernel` sources with the same `kallsyms`
+    /// but a different `kernel_image`).
+    #[test]
+    fn distinct_keys_do_not_alias() {
+        let cache = ResolverCache::new(2);
+        let builds = Cell::new(0);
+
+        let a = CacheKey::Kernel {
+            kallsyms: Some(PathBuf::from("/proc/kallsyms")),
+            kernel_image: Some(PathBuf::from("/boot/vmlinux-a")),
+        };
+        let b = CacheKey::Kernel {
+            kallsyms: Some(PathBuf::from("/proc/kallsyms")),
+            kernel_image: Some(PathBuf::from("/boot/vmlinux-b")),
+        };
+
+        let _resolver_a = cache.get_or_insert_with(a, || counting_resolver(&builds)).unwrap();
+        let _resolver_b = cache.get_or_insert_with(b, || counting_resolver(&builds)).unwrap();
+
+        assert_eq!(builds.get(), 2);
+    }
+
+    /// Once the cache is at capacity, inserting one more entry evicts the
+    /// least-recently-used one.
+    #[test]
+    fn overflow_evicts_least_recently_used() {
+        let cache = ResolverCache::new(2);
+        let builds = Cell::new(0);
+
+        let key_a = CacheKey::Path(PathBuf::from("/bin/a"));
+        let key_b = CacheKey::Path(PathBuf::from("/bin/b"));
+        let key_c = CacheKey::Path(PathBuf::from("/bin/c"));
+
+        let _a = cache.get_or_insert_with(key_a.clone(), || counting_resolver(&builds)).unwrap();
+        let _b = cache.get_or_insert_with(key_b, || counting_resolver(&builds)).unwrap();
+        // Inserting a third entry evicts `key_a`, the least-recently used.
+        let _c = cache.get_or_insert_with(key_c, || counting_resolver(&builds)).unwrap();
+        assert_eq!(builds.get(), 3);
+
+        // `key_a` is gone, so fetching it again must build a fresh resolver.
+        let _a_again = cache.get_or_insert_with(key_a, || counting_resolver(&builds)).unwrap();
+        assert_eq!(builds.get(), 4);
+    }
+
+    /// A capacity of `0` disables caching outright.
+    #[test]
+    fn zero_capacity_disables_caching() {
+        let cache = ResolverCache::new(0);
+        let builds = Cell::new(0);
+        let key = CacheKey::Path(PathBuf::from("/bin/a.out"));
+
+        let _first = cache.get_or_insert_with(key.clone(), || counting_resolver(&builds)).unwrap();
+        let _second = cache.get_or_insert_with(key, || counting_resolver(&builds)).unwrap();
+
+        assert_eq!(builds.get(), 2);
+    }
+}
@@ -0,0 +1,70 @@
+/// The degree of symbol name demangling a [`Symbolizer`][super::Symbolizer]
+/// should perform.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Demangle {
+    /// Do not demangle symbol names.
+    #[default]
+    None,
+    /// Demangle Rust (v0 and "legacy") symbol names.
+    Rust,
+    /// Demangle Rust as well as Itanium ABI C++ symbol names.
+    ///
+    /// MSVC decorated names are not currently supported.
+    Cpp,
+}
+
+/// Demangle `name` according to `flavor`, returning `None` if `name` isn't a
+/// mangled name recognized by the selected flavor (or demangling is
+/// disabled).
+pub(crate) fn demangle(name: &str, flavor: Demangle) -> Option<String> {
+    match flavor {
+        Demangle::None => None,
+        Demangle::Rust => demangle_rust(name),
+        Demangle::Cpp => demangle_rust(name).or_else(|| demangle_cpp(name)),
+    }
+}
+
+fn demangle_rust(name: &str) -> Option<String> {
+    rustc_demangle::try_demangle(name)
+        .ok()
+        .map(|demangled| format!("{demangled:#}"))
+}
+
+fn demangle_cpp(name: &str) -> Option<String> {
+    cpp_demangle::Symbol::new(name)
+        .ok()
+        .and_then(|symbol| symbol.demangle(&Default::default()).ok())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The mangled (v0) form of `example::function`.
+    const MANGLED_RUST: &str = "_RNvCsbdRDyALLnXW_7example8function";
+    /// The mangled (Itanium ABI) form of `example::function()`.
+    const MANGLED_CPP: &str = "_ZN7example8functionEv";
+
+    #[test]
+    fn none_never_demangles() {
+        assert_eq!(demangle(MANGLED_RUST, Demangle::None), None);
+        assert_eq!(demangle(MANGLED_CPP, Demangle::None), None);
+    }
+
+    #[test]
+    fn rust_demangles_rust_names_only() {
+        let demangled = demangle(MANGLED_RUST, Demangle::Rust).unwrap();
+        assert_eq!(demangled, "example::function");
+        assert_eq!(demangle(MANGLED_CPP, Demangle::Rust), None);
+    }
+
+    #[test]
+    fn cpp_demangles_both_rust_and_itanium_names() {
+        let demangled = demangle(MANGLED_RUST, Demangle::Cpp).unwrap();
+        assert_eq!(demangled, "example::function");
+
+        let demangled = demangle(MANGLED_CPP, Demangle::Cpp).unwrap();
+        assert_eq!(demangled, "example::function()");
+    }
+}
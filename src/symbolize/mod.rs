@@ -52,16 +52,23 @@
 //! }
 //! ```
 
+mod cache;
+mod demangle;
 mod source;
 mod symbolizer;
 
 use std::path::PathBuf;
 
+pub use demangle::Demangle;
+pub use source::Breakpad;
+pub use source::BreakpadData;
+pub use source::BreakpadFile;
 pub use source::Elf;
 pub use source::Gsym;
 pub use source::GsymData;
 pub use source::GsymFile;
 pub use source::Kernel;
+pub use source::Minidump;
 pub use source::Process;
 pub use source::Source;
 pub use symbolizer::Builder;
@@ -69,8 +76,14 @@ pub use symbolizer::SymbolizedResult;
 pub use symbolizer::Symbolizer;
 
 
-pub(crate) struct AddrLineInfo {
+/// Source line information corresponding to an address, as resolved by a
+/// [`SymResolver`][crate::SymResolver].
+#[derive(Clone, Debug)]
+pub struct AddrLineInfo {
+    /// The path of the file the address belongs to.
     pub path: PathBuf,
+    /// The line number the address belongs to.
     pub line: usize,
+    /// The column number the address belongs to.
     pub column: usize,
 }
@@ -2,8 +2,10 @@ use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use crate::Pid;
+use crate::SymResolver;
 
 #[cfg(doc)]
 use super::Symbolizer;
@@ -177,10 +179,101 @@ impl From<GsymFile> for Source<'static> {
 }
 
 
+#[derive(Clone, Debug)]
+pub enum Breakpad<'dat> {
+    /// "Raw" Breakpad symbol data.
+    Data(BreakpadData<'dat>),
+    /// A Breakpad symbol file.
+    File(BreakpadFile),
+}
+
+/// Breakpad symbol data.
+#[derive(Clone, Debug)]
+pub struct BreakpadData<'dat> {
+    /// The "raw" Breakpad symbol data.
+    pub data: &'dat [u8],
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub(crate) _non_exhaustive: (),
+}
+
+impl<'dat> BreakpadData<'dat> {
+    /// Create a new [`BreakpadData`] object, referencing the provided data.
+    pub fn new(data: &'dat [u8]) -> Self {
+        Self {
+            data,
+            _non_exhaustive: (),
+        }
+    }
+}
+
+impl<'dat> From<BreakpadData<'dat>> for Source<'dat> {
+    fn from(breakpad: BreakpadData<'dat>) -> Self {
+        Source::Breakpad(Breakpad::Data(breakpad))
+    }
+}
+
+
+/// A Breakpad symbol (`.sym`) file.
+#[derive(Clone, Debug)]
+pub struct BreakpadFile {
+    /// The path to the Breakpad symbol file.
+    pub path: PathBuf,
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub(crate) _non_exhaustive: (),
+}
+
+impl BreakpadFile {
+    /// Create a new [`BreakpadFile`] object, referencing the provided path.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            _non_exhaustive: (),
+        }
+    }
+}
+
+impl From<BreakpadFile> for Source<'static> {
+    fn from(breakpad: BreakpadFile) -> Self {
+        Source::Breakpad(Breakpad::File(breakpad))
+    }
+}
+
+
+/// A minidump (crash dump) file capturing a process' state at some point in
+/// the past.
+#[derive(Clone, Debug)]
+pub struct Minidump {
+    /// The path to the minidump file.
+    pub path: PathBuf,
+    /// The struct is non-exhaustive and open to extension.
+    #[doc(hidden)]
+    pub(crate) _non_exhaustive: (),
+}
+
+impl Minidump {
+    /// Create a new [`Minidump`] object, referencing the provided path.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            _non_exhaustive: (),
+        }
+    }
+}
+
+impl From<Minidump> for Source<'static> {
+    fn from(minidump: Minidump) -> Self {
+        Source::Minidump(minidump)
+    }
+}
+
+
 /// The description of a source of symbols and debug information.
 ///
 /// The source of symbols and debug information can be an ELF file, kernel
-/// image, or process.
+/// image, process, Gsym file, Breakpad symbol file, minidump, or a
+/// user-supplied [`SymResolver`].
 #[derive(Clone, Debug)]
 #[non_exhaustive]
 pub enum Source<'dat> {
@@ -192,6 +285,20 @@ pub enum Source<'dat> {
     Process(Process),
     /// A Gsym file.
     Gsym(Gsym<'dat>),
+    /// A Breakpad symbol file.
+    Breakpad(Breakpad<'dat>),
+    /// A minidump file capturing the state of a process that may no longer
+    /// exist.
+    Minidump(Minidump),
+    /// A user-supplied symbol resolver, e.g., for a JIT code map or an
+    /// in-memory symbol table that does not correspond to an on-disk file.
+    Custom(Arc<dyn SymResolver>),
+}
+
+impl<'dat> From<Arc<dyn SymResolver>> for Source<'dat> {
+    fn from(resolver: Arc<dyn SymResolver>) -> Self {
+        Source::Custom(resolver)
+    }
 }
 
 
@@ -0,0 +1,534 @@
+use std::fs::read;
+use std::fs::read_to_string;
+use std::io::Error;
+use std::io::ErrorKind;
+use std::io::Result;
+use std::path::Path;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::breakpad::BreakpadResolver;
+use crate::elf::ElfResolver;
+use crate::gsym::GsymResolver;
+use crate::inspect::FindAddrOpts;
+use crate::inspect::SymInfo;
+use crate::kernel::KernelResolver;
+use crate::ksym::KSymResolver;
+use crate::symbolize::cache::CacheKey;
+use crate::symbolize::cache::ResolverCache;
+use crate::symbolize::demangle::demangle;
+use crate::symbolize::source::Breakpad;
+use crate::symbolize::source::Gsym;
+use crate::symbolize::source::Source;
+use crate::symbolize::AddrLineInfo;
+use crate::symbolize::Demangle;
+use crate::Addr;
+use crate::Pid;
+use crate::SymResolver;
+
+/// The default number of resolvers a [`Symbolizer`] retains in its LRU
+/// cache.
+const DEFAULT_CACHE_CAPACITY: usize = 1024;
+
+/// Adapts a user-supplied [`Arc<dyn SymResolver>`] to the [`SymResolver`]
+/// trait so it can be used alongside the built-in resolvers.
+#[derive(Debug)]
+struct CustomResolver(Arc<dyn SymResolver>);
+
+impl SymResolver for CustomResolver {
+    fn find_syms(&self, addr: Addr) -> Result<Vec<(&str, Addr)>> {
+        self.0.find_syms(addr)
+    }
+
+    fn find_addr(&self, name: &str, opts: &FindAddrOpts) -> Result<Vec<SymInfo>> {
+        self.0.find_addr(name, opts)
+    }
+
+    fn find_line_info(&self, addr: Addr) -> Result<Option<AddrLineInfo>> {
+        self.0.find_line_info(addr)
+    }
+
+    fn addr_file_off(&self, addr: Addr) -> Option<u64> {
+        self.0.addr_file_off(addr)
+    }
+}
+
+
+/// The result of symbolizing an address.
+#[derive(Clone, Debug)]
+pub struct SymbolizedResult {
+    /// The symbol name that an address may have belonged to.
+    ///
+    /// This is the raw, potentially mangled, linkage name as reported by the
+    /// symbol source.
+    pub symbol: String,
+    /// The demangled version of [`symbol`][Self::symbol], present when
+    /// demangling was requested via [`Builder::set_demangle`] and `symbol`
+    /// was recognized as a mangled name.
+    pub demangled: Option<String>,
+    /// The address at which the symbol is located.
+    pub addr: Addr,
+    /// The path of the file in which the symbol is defined.
+    pub path: PathBuf,
+    /// The line number of the symbol.
+    pub line: usize,
+    /// The column number of the symbol.
+    pub column: usize,
+}
+
+impl SymbolizedResult {
+    fn new(
+        name: &str,
+        sym_addr: Addr,
+        line_info: Option<AddrLineInfo>,
+        flavor: Demangle,
+    ) -> Self {
+        let (path, line, column) = match line_info {
+            Some(AddrLineInfo { path, line, column }) => (path, line, column),
+            None => (PathBuf::new(), 0, 0),
+        };
+        Self {
+            symbol: name.to_string(),
+            demangled: demangle(name, flavor),
+            addr: sym_addr,
+            path,
+            line,
+            column,
+        }
+    }
+}
+
+
+/// A builder for configurable construction of [`Symbolizer`] objects.
+///
+/// By default, symbol names are not demangled, debuginfod servers are taken
+/// from the `DEBUGINFOD_URLS` environment variable, no extra debug
+/// directories are searched, and up to 1024 resolvers are cached.
+#[derive(Clone, Debug)]
+pub struct Builder {
+    /// Debuginfod servers to query when a source lacks local debug
+    /// information.
+    ///
+    /// `None` means none were explicitly configured, so the
+    /// `DEBUGINFOD_URLS` environment variable is consulted instead; `Some`
+    /// (including an empty list) is used as-is, letting callers explicitly
+    /// disable debuginfod regardless of the environment.
+    debuginfod_urls: Option<Vec<String>>,
+    /// Additional directories to search for `.gnu_debuglink` debug files, on
+    /// top of the standard locations.
+    debug_dirs: Vec<PathBuf>,
+    /// The degree of symbol name demangling to perform.
+    demangle: Demangle,
+    /// The number of resolvers retained in the LRU resolver cache.
+    cache_capacity: usize,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            debuginfod_urls: None,
+            debug_dirs: Vec::new(),
+            demangle: Demangle::default(),
+            cache_capacity: DEFAULT_CACHE_CAPACITY,
+        }
+    }
+}
+
+impl Builder {
+    /// Create a new [`Builder`] with default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set an explicit list of debuginfod server URLs to consult when a
+    /// source is missing local debug information.
+    ///
+    /// If unset, the `DEBUGINFOD_URLS` environment variable is consulted
+    /// instead. Passing an empty list disables debuginfod outright, even if
+    /// `DEBUGINFOD_URLS` is set in the environment.
+    pub fn set_debuginfod_urls<I, S>(mut self, urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.debuginfod_urls = Some(urls.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Add directories to search for split debug files referenced via
+    /// `.gnu_debuglink`, in addition to the same directory as the binary,
+    /// its `.debug` subdirectory, and `/usr/lib/debug/`.
+    pub fn set_debug_dirs<I, P>(mut self, dirs: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        self.debug_dirs = dirs.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the degree of symbol name demangling the [`Symbolizer`] should
+    /// perform on resolved symbol names.
+    pub fn set_demangle(mut self, demangle: Demangle) -> Self {
+        self.demangle = demangle;
+        self
+    }
+
+    /// Set the number of resolvers (one per module or process mapping) the
+    /// [`Symbolizer`] retains in its LRU cache. A capacity of `0` disables
+    /// caching, rebuilding a resolver on every `symbolize` call.
+    pub fn set_cache_capacity(mut self, capacity: usize) -> Self {
+        self.cache_capacity = capacity;
+        self
+    }
+
+    /// Create the [`Symbolizer`] object.
+    pub fn build(self) -> Symbolizer {
+        let Builder {
+            debuginfod_urls,
+            debug_dirs,
+            demangle,
+            cache_capacity,
+        } = self;
+        Symbolizer {
+            debuginfod_urls,
+            debug_dirs,
+            demangle,
+            cache: ResolverCache::new(cache_capacity),
+        }
+    }
+}
+
+
+/// A type that can symbolize addresses.
+///
+/// Objects of this type can be used to symbolize addresses. Pass it a
+/// [`Source`] to describe where the symbols should be looked up and the
+/// addresses that should be translated.
+///
+/// Resolvers are expensive to build (they read and parse ELF files), so a
+/// `Symbolizer` retains an LRU cache of them across `symbolize` calls; see
+/// [`Builder::set_cache_capacity`].
+#[derive(Debug)]
+pub struct Symbolizer {
+    debuginfod_urls: Option<Vec<String>>,
+    debug_dirs: Vec<PathBuf>,
+    demangle: Demangle,
+    cache: ResolverCache,
+}
+
+impl Default for Symbolizer {
+    fn default() -> Self {
+        Builder::default().build()
+    }
+}
+
+/// A single entry of `/proc/<pid>/maps`, as far as we care about it here.
+struct MapsEntry {
+    start: Addr,
+    end: Addr,
+    offset: u64,
+    path: PathBuf,
+}
+
+fn parse_maps(pid: Pid) -> Result<Vec<MapsEntry>> {
+    let path = match pid {
+        Pid::Slf => PathBuf::from("/proc/self/maps"),
+        Pid::Pid(pid) => PathBuf::from(format!("/proc/{pid}/maps")),
+    };
+    let content = read_to_string(&path)?;
+    let mut entries = Vec::new();
+
+    for line in content.lines() {
+        let mut fields = line.split_whitespace();
+        let range = fields
+            .next()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "maps entry without address range"))?;
+        let (start, end) = range
+            .split_once('-')
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "malformed maps address range"))?;
+        let start = u64::from_str_radix(start, 16)
+            .map_err(|_err| Error::new(ErrorKind::InvalidData, "malformed maps start address"))?
+            as Addr;
+        let end = u64::from_str_radix(end, 16)
+            .map_err(|_err| Error::new(ErrorKind::InvalidData, "malformed maps end address"))?
+            as Addr;
+        // Permissions field; unused.
+        let _perms = fields.next();
+        let offset = fields
+            .next()
+            .and_then(|offset| u64::from_str_radix(offset, 16).ok())
+            .unwrap_or(0);
+        // `dev` and `inode` fields; unused.
+        let _dev = fields.next();
+        let _inode = fields.next();
+        let path = match fields.next() {
+            Some(path) if path.starts_with('/') => PathBuf::from(path),
+            _ => continue,
+        };
+
+        entries.push(MapsEntry {
+            start,
+            end,
+            offset,
+            path,
+        });
+    }
+
+    Ok(entries)
+}
+
+impl Symbolizer {
+    /// Create a new [`Symbolizer`].
+    pub fn new() -> Self {
+        Builder::new().build()
+    }
+
+    /// Retrieve the list of debuginfod server URLs to consult, preferring an
+    /// explicitly configured list (which may be empty, disabling debuginfod)
+    /// over the `DEBUGINFOD_URLS` environment variable.
+    fn debuginfod_urls(&self) -> Vec<String> {
+        if let Some(urls) = &self.debuginfod_urls {
+            return urls.clone()
+        }
+
+        std::env::var("DEBUGINFOD_URLS")
+            .ok()
+            .map(|urls| urls.split_whitespace().map(str::to_string).collect())
+            .unwrap_or_default()
+    }
+
+    /// Open an [`ElfResolver`] for `path`, following `.gnu_debuglink`/
+    /// `.build-id` to a split debug file and, if that still doesn't turn up
+    /// debug information, falling back to debuginfod.
+    fn elf_resolver(&self, path: &Path) -> Result<ElfResolver> {
+        match ElfResolver::open_with_debug_dirs(path, &self.debug_dirs) {
+            Ok(resolver) if resolver.has_debug_info() => Ok(resolver),
+            fallback => {
+                let urls = self.debuginfod_urls();
+                let build_id = fallback.as_ref().ok().and_then(|resolver| resolver.build_id());
+                match (urls.is_empty(), build_id) {
+                    (false, Some(build_id)) => {
+                        match crate::debuginfod::fetch_debug_info(build_id, &urls) {
+                            Ok(debug_path) => ElfResolver::open(&debug_path),
+                            Err(_err) => fallback,
+                        }
+                    }
+                    _ => fallback,
+                }
+            }
+        }
+    }
+
+    /// Build (or, on a cache hit, reuse) the resolver for `path`, caching it
+    /// as an [`ElfResolver`] under [`CacheKey::Path`].
+    fn cached_elf_resolver(&self, path: &Path) -> Result<Rc<dyn SymResolver>> {
+        self.cache.get_or_insert_with(CacheKey::Path(path.to_path_buf()), || {
+            Ok(Rc::new(self.elf_resolver(path)?) as Rc<dyn SymResolver>)
+        })
+    }
+
+    fn resolver_for_source(&self, src: &Source<'_>) -> Result<Rc<dyn SymResolver>> {
+        match src {
+            Source::Elf(elf) => self.cached_elf_resolver(&elf.path),
+            Source::Kernel(kernel) => {
+                let key = CacheKey::Kernel {
+                    kallsyms: kernel.kallsyms.clone(),
+                    kernel_image: kernel.kernel_image.clone(),
+                };
+                self.cache.get_or_insert_with(key, || {
+                    let ksym_resolver = kernel
+                        .kallsyms
+                        .as_deref()
+                        .or(Some(Path::new("/proc/kallsyms")))
+                        .and_then(|path| KSymResolver::load(path).ok())
+                        .map(Rc::new);
+                    let elf_resolver = kernel
+                        .kernel_image
+                        .clone()
+                        .or_else(|| {
+                            let release = read_to_string("/proc/sys/kernel/osrelease").ok()?;
+                            let release = release.trim();
+                            [
+                                format!("/boot/vmlinux-{release}"),
+                                format!("/usr/lib/debug/boot/vmlinux-{release}"),
+                            ]
+                            .into_iter()
+                            .map(PathBuf::from)
+                            .find(|path| path.exists())
+                        })
+                        .and_then(|path| self.elf_resolver(&path).ok());
+                    let resolver = KernelResolver::new(ksym_resolver, elf_resolver)?;
+                    Ok(Rc::new(resolver) as Rc<dyn SymResolver>)
+                })
+            }
+            Source::Gsym(Gsym::File(gsym)) => {
+                self.cache.get_or_insert_with(CacheKey::Path(gsym.path.clone()), || {
+                    Ok(Rc::new(GsymResolver::open(&gsym.path)?) as Rc<dyn SymResolver>)
+                })
+            }
+            Source::Gsym(Gsym::Data(gsym)) => {
+                Ok(Rc::new(GsymResolver::parse(gsym.data)?) as Rc<dyn SymResolver>)
+            }
+            Source::Breakpad(Breakpad::File(breakpad)) => {
+                self.cache.get_or_insert_with(CacheKey::Path(breakpad.path.clone()), || {
+                    Ok(Rc::new(BreakpadResolver::new(&breakpad.path)?) as Rc<dyn SymResolver>)
+                })
+            }
+            Source::Breakpad(Breakpad::Data(breakpad)) => {
+                Ok(Rc::new(BreakpadResolver::from_data(breakpad.data)?) as Rc<dyn SymResolver>)
+            }
+            Source::Custom(resolver) => {
+                Ok(Rc::new(CustomResolver(resolver.clone())) as Rc<dyn SymResolver>)
+            }
+            Source::Process(_) => Err(Error::new(
+                ErrorKind::InvalidInput,
+                "process sources must be symbolized address by address",
+            )),
+            Source::Minidump(_) => Err(Error::new(
+                ErrorKind::InvalidInput,
+                "minidump sources must be symbolized address by address",
+            )),
+        }
+    }
+
+    fn symbolize_process(&self, pid: Pid, addrs: &[Addr]) -> Result<Vec<Vec<SymbolizedResult>>> {
+        let maps = parse_maps(pid)?;
+
+        addrs
+            .iter()
+            .map(|addr| {
+                let entry = match maps
+                    .iter()
+                    .find(|entry| *addr >= entry.start && *addr < entry.end)
+                {
+                    Some(entry) => entry,
+                    None => return Ok(Vec::new()),
+                };
+
+                let key = CacheKey::ProcessModule {
+                    pid,
+                    path: entry.path.clone(),
+                    start: entry.start,
+                };
+                let resolver = self.cache.get_or_insert_with(key, || {
+                    Ok(Rc::new(self.elf_resolver(&entry.path)?) as Rc<dyn SymResolver>)
+                })?;
+
+                let file_off = (*addr - entry.start) as u64 + entry.offset;
+                let syms = resolver.find_syms(file_off as Addr)?;
+                syms.into_iter()
+                    .map(|(name, sym_addr)| {
+                        let line_info = resolver.find_line_info(file_off as Addr)?;
+                        Ok(SymbolizedResult::new(name, sym_addr, line_info, self.demangle))
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect()
+    }
+
+    /// Symbolize addresses captured in a minidump, mapping each one to the
+    /// module it falls into according to the dump's module list and
+    /// resolving it the same way [`Source::Elf`] would.
+    fn symbolize_minidump(&self, path: &Path, addrs: &[Addr]) -> Result<Vec<Vec<SymbolizedResult>>> {
+        let data = read(path)?;
+        let modules = crate::minidump::parse_modules(&data)?;
+
+        addrs
+            .iter()
+            .map(|addr| {
+                let module = match modules
+                    .iter()
+                    .find(|module| *addr >= module.base && *addr < module.base + module.size as Addr)
+                {
+                    Some(module) => module,
+                    None => return Ok(Vec::new()),
+                };
+
+                let resolver = self.cached_elf_resolver(&module.path)?;
+
+                let file_off = *addr - module.base;
+                let syms = resolver.find_syms(file_off)?;
+                syms.into_iter()
+                    .map(|(name, sym_addr)| {
+                        let line_info = resolver.find_line_info(file_off)?;
+                        Ok(SymbolizedResult::new(name, sym_addr, line_info, self.demangle))
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect()
+    }
+
+    /// Symbolize a list of addresses using the provided [`Source`].
+    pub fn symbolize(
+        &self,
+        src: &Source<'_>,
+        addrs: &[Addr],
+    ) -> Result<Vec<Vec<SymbolizedResult>>> {
+        if let Source::Process(process) = src {
+            return self.symbolize_process(process.pid, addrs)
+        }
+        if let Source::Minidump(minidump) = src {
+            return self.symbolize_minidump(&minidump.path, addrs)
+        }
+
+        let resolver = self.resolver_for_source(src)?;
+
+        addrs
+            .iter()
+            .map(|addr| {
+                let syms = resolver.find_syms(*addr)?;
+                syms.into_iter()
+                    .map(|(name, sym_addr)| {
+                        let line_info = resolver.find_line_info(*addr)?;
+                        Ok(SymbolizedResult::new(name, sym_addr, line_info, self.demangle))
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A stub [`SymResolver`] that reports a single, fixed symbol for any
+    /// address, so tests can tell whether `Source::Custom` reached it.
+    #[derive(Debug)]
+    struct StubResolver;
+
+    impl SymResolver for StubResolver {
+        fn find_syms(&self, addr: Addr) -> Result<Vec<(&str, Addr)>> {
+            Ok(vec![("stub_symbol", addr)])
+        }
+
+        fn find_addr(&self, _name: &str, _opts: &FindAddrOpts) -> Result<Vec<SymInfo>> {
+            Ok(Vec::new())
+        }
+
+        fn find_line_info(&self, _addr: Addr) -> Result<Option<AddrLineInfo>> {
+            Ok(None)
+        }
+
+        fn addr_file_off(&self, _addr: Addr) -> Option<u64> {
+            None
+        }
+    }
+
+    /// `Symbolizer::symbolize` on a `Source::Custom` must dispatch to the
+    /// user-supplied resolver rather than any built-in one.
+    #[test]
+    fn custom_source_dispatches_to_stub_resolver() {
+        let symbolizer = Symbolizer::new();
+        let src = Source::Custom(Arc::new(StubResolver) as Arc<dyn SymResolver>);
+
+        let results = symbolizer.symbolize(&src, &[0x1000]).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].len(), 1);
+        assert_eq!(results[0][0].symbol, "stub_symbol");
+        assert_eq!(results[0][0].addr, 0x1000);
+    }
+}